@@ -1,5 +1,13 @@
 use std::time::Instant;
 
+pub(crate) mod select;
+
+pub mod pipeline_driver;
+pub use pipeline_driver::PipelineDriver;
+
+pub mod transport_runner;
+pub use transport_runner::{OutboundSource, Socket, TransportRunner};
+
 pub trait Handler {
     /// Associated input event type
     type Ein: 'static;
@@ -0,0 +1,390 @@
+//! A small bounded thread pool for offloading synchronous work.
+//!
+//! Mirrors tokio's blocking pool: jobs are pushed onto a shared queue,
+//! worker threads are spawned on demand up to a configurable maximum,
+//! and idle workers are reaped after sitting without work for the
+//! configured keep-alive.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+use super::runtime::BlockingPool as BlockingPoolTrait;
+
+/// Tuning knobs for a [`ThreadPool`].
+#[derive(Debug, Clone, Copy)]
+pub struct BlockingPoolConfig {
+    /// Maximum number of worker threads kept alive at once.
+    pub max_threads: usize,
+    /// How long an idle worker waits for work before exiting.
+    pub keep_alive: Duration,
+}
+
+impl Default for BlockingPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_threads: 4,
+            keep_alive: Duration::from_secs(10),
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Shared {
+    queue: Mutex<VecDeque<Job>>,
+    condvar: Condvar,
+    live_threads: AtomicUsize,
+    config: BlockingPoolConfig,
+}
+
+/// A bounded pool of worker threads for running blocking closures.
+///
+/// Threads are spawned lazily as work arrives and reaped once idle for
+/// longer than [`BlockingPoolConfig::keep_alive`], so an idle pool costs
+/// nothing beyond the queue itself.
+#[derive(Clone)]
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        Self::new(BlockingPoolConfig::default())
+    }
+}
+
+impl ThreadPool {
+    /// Creates a new pool with the given configuration. No threads are
+    /// spawned until the first job arrives.
+    pub fn new(config: BlockingPoolConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                queue: Mutex::new(VecDeque::new()),
+                condvar: Condvar::new(),
+                live_threads: AtomicUsize::new(0),
+                config,
+            }),
+        }
+    }
+
+    /// Runs `f` on the pool and returns a handle to its result.
+    pub fn spawn_blocking<F, T>(&self, f: F) -> BlockingTask<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let inner = Arc::new(TaskInner {
+            state: Mutex::new(TaskState::Pending(None)),
+        });
+        let job_inner = Arc::clone(&inner);
+        let job: Job = Box::new(move || {
+            // Catch the closure's panic here rather than letting it unwind
+            // the worker thread: an unwound worker never reaches the
+            // `live_threads.fetch_sub` below (see `worker_loop`), and the
+            // task would otherwise be left `Pending` forever since nothing
+            // would ever store a result for it.
+            let new_state = match panic::catch_unwind(AssertUnwindSafe(f)) {
+                Ok(value) => TaskState::Ready(value),
+                Err(payload) => TaskState::Panicked(payload),
+            };
+            let mut state = job_inner.state.lock().unwrap();
+            let waker = match std::mem::replace(&mut *state, new_state) {
+                TaskState::Pending(waker) => waker,
+                TaskState::Ready(_) | TaskState::Panicked(_) | TaskState::Taken => {
+                    unreachable!("job runs exactly once")
+                }
+            };
+            drop(state);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            queue.push_back(job);
+        }
+        self.shared.condvar.notify_one();
+        self.maybe_spawn_worker();
+
+        BlockingTask { inner }
+    }
+
+    fn maybe_spawn_worker(&self) {
+        let live = self.shared.live_threads.load(Ordering::SeqCst);
+        if live >= self.shared.config.max_threads {
+            return;
+        }
+        if self
+            .shared
+            .live_threads
+            .compare_exchange(live, live + 1, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            // Lost the race with another spawn; the thread that won will
+            // pick up the work we just queued.
+            return;
+        }
+
+        let shared = Arc::clone(&self.shared);
+        thread::spawn(move || worker_loop(shared));
+    }
+}
+
+fn worker_loop(shared: Arc<Shared>) {
+    let mut queue = shared.queue.lock().unwrap();
+    loop {
+        if let Some(job) = queue.pop_front() {
+            drop(queue);
+            // `job` already catches `f`'s panic internally, but guard
+            // against it unwinding anyway (e.g. a poisoned lock) so
+            // `live_threads` is decremented on every exit path, not just
+            // the idle-timeout one below.
+            if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                shared.live_threads.fetch_sub(1, Ordering::SeqCst);
+                return;
+            }
+            queue = shared.queue.lock().unwrap();
+            continue;
+        }
+
+        let (guard, timeout) = shared
+            .condvar
+            .wait_timeout(queue, shared.config.keep_alive)
+            .unwrap();
+        queue = guard;
+
+        if timeout.timed_out() && queue.is_empty() {
+            // Decide to exit and record it in the same critical section
+            // that checked the queue, still holding `queue`'s lock. A
+            // `spawn_blocking` call that is concurrently pushing a job
+            // takes this same lock, so it either pushes before we get
+            // here (we'll see the job above and keep running) or it
+            // blocks until we've released the lock below, by which
+            // point `live_threads` already reflects this worker's exit
+            // and `maybe_spawn_worker` will correctly spawn a
+            // replacement instead of assuming we're still live.
+            shared.live_threads.fetch_sub(1, Ordering::SeqCst);
+            return;
+        }
+    }
+}
+
+impl BlockingPoolTrait for ThreadPool {
+    type BlockingHandle<T: 'static> = BlockingTask<T>;
+
+    fn spawn_blocking<F, T>(&self, f: F) -> Self::BlockingHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        ThreadPool::spawn_blocking(self, f)
+    }
+}
+
+enum TaskState<T> {
+    Pending(Option<Waker>),
+    Ready(T),
+    Panicked(Box<dyn Any + Send>),
+    Taken,
+}
+
+struct TaskInner<T> {
+    state: Mutex<TaskState<T>>,
+}
+
+/// A handle to a closure running on a [`ThreadPool`].
+///
+/// Resolves to the closure's return value when awaited. Dropping the
+/// handle does not cancel the job; it runs to completion regardless. If
+/// the closure panicked, awaiting the task resumes that panic instead of
+/// hanging forever.
+pub struct BlockingTask<T> {
+    inner: Arc<TaskInner<T>>,
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.inner.state.lock().unwrap();
+        match &mut *state {
+            TaskState::Ready(_) => {
+                match std::mem::replace(&mut *state, TaskState::Taken) {
+                    TaskState::Ready(value) => Poll::Ready(value),
+                    _ => unreachable!(),
+                }
+            }
+            TaskState::Panicked(_) => match std::mem::replace(&mut *state, TaskState::Taken) {
+                TaskState::Panicked(payload) => panic::resume_unwind(payload),
+                _ => unreachable!(),
+            },
+            TaskState::Pending(waker) => {
+                *waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            TaskState::Taken => panic!("BlockingTask polled after completion"),
+        }
+    }
+}
+
+thread_local! {
+    // Set by `LocalExecutorBuilder::run` for the duration of its `fut`
+    // when the builder was given a `BlockingPoolConfig`, so `spawn_blocking`
+    // can use a per-executor pool instead of always falling back to the
+    // shared process-wide default.
+    static ACTIVE_POOL: RefCell<Option<ThreadPool>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` on the active blocking pool: the executor-scoped pool set by
+/// [`LocalExecutorBuilder::blocking_pool_size`]/[`blocking_keep_alive`] if
+/// one is active, otherwise the shared process-wide default.
+///
+/// This is the free-function counterpart to [`spawn_local`](crate::spawn_local)
+/// for synchronous work (DNS lookups, file I/O, CPU-heavy crypto) that would
+/// otherwise stall the single-threaded executor. Use [`ThreadPool::new`]
+/// directly if you'd rather own a pool outright instead of going through
+/// the active one.
+///
+/// [`LocalExecutorBuilder::blocking_pool_size`]: super::LocalExecutorBuilder::blocking_pool_size
+/// [`blocking_keep_alive`]: super::LocalExecutorBuilder::blocking_keep_alive
+pub fn spawn_blocking<F, T>(f: F) -> BlockingTask<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = ACTIVE_POOL.with(|active| active.borrow().clone());
+    match pool {
+        Some(pool) => pool.spawn_blocking(f),
+        None => default_pool().spawn_blocking(f),
+    }
+}
+
+fn default_pool() -> &'static ThreadPool {
+    static DEFAULT: std::sync::OnceLock<ThreadPool> = std::sync::OnceLock::new();
+    DEFAULT.get_or_init(ThreadPool::default)
+}
+
+/// Sets the active blocking pool for the duration of `f`, restoring
+/// whatever was active beforehand afterward. Used by
+/// `LocalExecutorBuilder::run` to apply a per-executor `BlockingPoolConfig`.
+pub(crate) fn with_active_pool<R>(pool: Option<ThreadPool>, f: impl FnOnce() -> R) -> R {
+    let previous = ACTIVE_POOL.with(|active| active.replace(pool));
+    let result = f();
+    ACTIVE_POOL.with(|active| *active.borrow_mut() = previous);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = std::pin::pin!(fut);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(value) => return value,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn spawn_blocking_returns_value() {
+        let pool = ThreadPool::new(BlockingPoolConfig {
+            max_threads: 2,
+            keep_alive: Duration::from_millis(50),
+        });
+        let task = pool.spawn_blocking(|| 1 + 1);
+        assert_eq!(block_on(task), 2);
+    }
+
+    #[test]
+    fn spawn_blocking_respects_max_threads() {
+        let pool = ThreadPool::new(BlockingPoolConfig {
+            max_threads: 2,
+            keep_alive: Duration::from_millis(50),
+        });
+        let concurrent = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let concurrent = Arc::clone(&concurrent);
+                let peak = Arc::clone(&peak);
+                pool.spawn_blocking(move || {
+                    let now = concurrent.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    concurrent.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            block_on(task);
+        }
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn idle_worker_is_reaped_and_replaced() {
+        let pool = ThreadPool::new(BlockingPoolConfig {
+            max_threads: 1,
+            keep_alive: Duration::from_millis(20),
+        });
+        block_on(pool.spawn_blocking(|| ()));
+        // Give the sole worker time to sit idle past keep_alive and reap
+        // itself.
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(pool.shared.live_threads.load(Ordering::SeqCst), 0);
+
+        // A job submitted after the pool has gone fully idle must still
+        // get a fresh worker spawned for it instead of hanging forever.
+        assert_eq!(block_on(pool.spawn_blocking(|| 42)), 42);
+    }
+
+    #[test]
+    fn panicking_job_resumes_the_panic_on_await() {
+        let pool = ThreadPool::new(BlockingPoolConfig::default());
+        let task = pool.spawn_blocking(|| -> i32 { panic!("boom") });
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| block_on(task)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn panicking_job_does_not_leak_the_live_thread_count() {
+        let pool = ThreadPool::new(BlockingPoolConfig {
+            max_threads: 1,
+            keep_alive: Duration::from_secs(60),
+        });
+        let task = pool.spawn_blocking(|| panic!("boom"));
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| block_on(task)));
+
+        // A job submitted right after a panic must still get a worker:
+        // the panic is caught inside the job, so the sole worker stays
+        // live (live_threads still counts it) and picks this job up.
+        assert_eq!(block_on(pool.spawn_blocking(|| 7)), 7);
+    }
+}
@@ -0,0 +1,242 @@
+//! Runtime-selectable backend dispatch.
+//!
+//! Previously `runtime-smol`/`runtime-tokio` were mutually exclusive:
+//! exactly one had to be enabled, and `pub use smol::*`/`pub use tokio::*`
+//! picked the active backend at compile time. Both can now be compiled
+//! in at once; [`RuntimeKind`] and the enum-dispatched
+//! [`LocalExecutorBuilder`] pick the active one at run time instead, so a
+//! library can ship both backends and let the end application choose.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use super::blocking::{with_active_pool, BlockingPoolConfig, ThreadPool};
+#[cfg(feature = "runtime-smol")]
+use super::smol;
+#[cfg(feature = "runtime-tokio")]
+use super::tokio;
+
+/// Selects which compiled-in backend a [`LocalExecutorBuilder`] runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeKind {
+    /// Run on smol's `LocalExecutor`.
+    #[cfg(feature = "runtime-smol")]
+    Smol,
+    /// Run on tokio's `LocalSet`.
+    #[cfg(feature = "runtime-tokio")]
+    Tokio,
+}
+
+impl Default for RuntimeKind {
+    /// Prefers smol when both backends are compiled in, matching the
+    /// previous default-feature behavior.
+    fn default() -> Self {
+        #[cfg(feature = "runtime-smol")]
+        {
+            RuntimeKind::Smol
+        }
+        #[cfg(not(feature = "runtime-smol"))]
+        {
+            RuntimeKind::Tokio
+        }
+    }
+}
+
+thread_local! {
+    static ACTIVE_KIND: Cell<Option<RuntimeKind>> = const { Cell::new(None) };
+}
+
+fn enter<R>(kind: RuntimeKind, f: impl FnOnce() -> R) -> R {
+    let previous = ACTIVE_KIND.with(|active| active.replace(Some(kind)));
+    let result = f();
+    ACTIVE_KIND.with(|active| active.set(previous));
+    result
+}
+
+#[derive(Default)]
+struct PendingConfig {
+    name: Option<String>,
+    core_id: Option<core_affinity::CoreId>,
+    blocking_pool: Option<BlockingPoolConfig>,
+}
+
+/// Builds and runs a local executor, choosing its backend at run time
+/// via [`RuntimeKind`] rather than at compile time via cargo features.
+pub struct LocalExecutorBuilder {
+    kind: Option<RuntimeKind>,
+    config: PendingConfig,
+}
+
+impl Default for LocalExecutorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalExecutorBuilder {
+    /// Creates a new builder using the default backend (see
+    /// [`RuntimeKind::default`]). Call [`Self::runtime`] to pick a
+    /// specific backend explicitly.
+    pub fn new() -> Self {
+        Self {
+            kind: None,
+            config: PendingConfig::default(),
+        }
+    }
+
+    /// Selects which compiled-in backend to run on.
+    pub fn runtime(mut self, kind: RuntimeKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Names the executor's thread, for diagnostics.
+    pub fn name(mut self, name: &str) -> Self {
+        self.config.name = Some(name.to_owned());
+        self
+    }
+
+    /// Pins the executor to the given CPU core.
+    pub fn core_id(mut self, core_id: core_affinity::CoreId) -> Self {
+        self.config.core_id = Some(core_id);
+        self
+    }
+
+    /// Sets the maximum number of worker threads this executor's
+    /// [`spawn_blocking`](super::spawn_blocking) calls use, instead of the
+    /// shared process-wide default pool.
+    pub fn blocking_pool_size(mut self, max_threads: usize) -> Self {
+        self.config
+            .blocking_pool
+            .get_or_insert_with(BlockingPoolConfig::default)
+            .max_threads = max_threads;
+        self
+    }
+
+    /// Sets how long this executor's blocking-pool workers sit idle
+    /// before exiting, instead of the shared process-wide default pool.
+    pub fn blocking_keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.config
+            .blocking_pool
+            .get_or_insert_with(BlockingPoolConfig::default)
+            .keep_alive = keep_alive;
+        self
+    }
+
+    /// Runs `fut` to completion on the selected backend, blocking the
+    /// calling thread until it resolves.
+    pub fn run<F: Future>(self, fut: F) -> F::Output {
+        let kind = self.kind.unwrap_or_default();
+        let PendingConfig {
+            name,
+            core_id,
+            blocking_pool,
+        } = self.config;
+        let pool = blocking_pool.map(ThreadPool::new);
+        enter(kind, move || {
+            with_active_pool(pool, move || match kind {
+                #[cfg(feature = "runtime-smol")]
+                RuntimeKind::Smol => {
+                    let mut builder = smol::LocalExecutorBuilder::new();
+                    if let Some(name) = name {
+                        builder = builder.name(&name);
+                    }
+                    if let Some(core_id) = core_id {
+                        builder = builder.core_id(core_id);
+                    }
+                    builder.run(fut)
+                }
+                #[cfg(feature = "runtime-tokio")]
+                RuntimeKind::Tokio => {
+                    let mut builder = tokio::LocalExecutorBuilder::new();
+                    if let Some(name) = name {
+                        builder = builder.name(&name);
+                    }
+                    if let Some(core_id) = core_id {
+                        builder = builder.core_id(core_id);
+                    }
+                    builder.run(fut)
+                }
+            })
+        })
+    }
+}
+
+/// Handle returned by [`spawn_local`]; resolves to the task's output.
+pub struct LocalTask<T>(Pin<Box<dyn Future<Output = T>>>);
+
+impl<T> Future for LocalTask<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// Spawns `fut` on whichever backend the enclosing
+/// [`LocalExecutorBuilder::run`] selected.
+///
+/// # Panics
+/// Panics if called outside of a running `LocalExecutorBuilder`.
+pub fn spawn_local<F>(fut: F) -> LocalTask<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    let kind = ACTIVE_KIND
+        .with(|active| active.get())
+        .expect("spawn_local called outside of a running LocalExecutorBuilder");
+    match kind {
+        #[cfg(feature = "runtime-smol")]
+        RuntimeKind::Smol => LocalTask(Box::pin(smol::spawn_local(fut))),
+        #[cfg(feature = "runtime-tokio")]
+        RuntimeKind::Tokio => LocalTask(Box::pin(tokio::spawn_local(fut))),
+    }
+}
+
+/// Handle returned by [`sleep`]/[`sleep_until`]; resolves when the
+/// deadline passes.
+pub struct LocalSleep(Pin<Box<dyn Future<Output = ()>>>);
+
+impl Future for LocalSleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// Sleeps until `deadline` on whichever backend the enclosing
+/// [`LocalExecutorBuilder::run`] selected, using that backend's own timer
+/// wheel rather than parking a blocking-pool thread.
+///
+/// # Panics
+/// Panics if called outside of a running `LocalExecutorBuilder`.
+pub fn sleep_until(deadline: Instant) -> LocalSleep {
+    let kind = ACTIVE_KIND
+        .with(|active| active.get())
+        .expect("sleep_until called outside of a running LocalExecutorBuilder");
+    match kind {
+        #[cfg(feature = "runtime-smol")]
+        RuntimeKind::Smol => LocalSleep(Box::pin(async move {
+            smol::Timer::at(deadline).await;
+        })),
+        #[cfg(feature = "runtime-tokio")]
+        RuntimeKind::Tokio => LocalSleep(Box::pin(
+            tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)),
+        )),
+    }
+}
+
+/// Sleeps for `duration` on whichever backend the enclosing
+/// [`LocalExecutorBuilder::run`] selected. Shorthand for
+/// `sleep_until(Instant::now() + duration)`.
+///
+/// # Panics
+/// Panics if called outside of a running `LocalExecutorBuilder`.
+pub fn sleep(duration: Duration) -> LocalSleep {
+    sleep_until(Instant::now() + duration)
+}
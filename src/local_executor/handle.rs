@@ -0,0 +1,75 @@
+//! A concrete [`Runtime`] implementor usable from inside any running
+//! [`LocalExecutorBuilder`].
+//!
+//! [`CompoundRuntime`] lets advanced callers assemble a `Runtime` from
+//! independent parts, but something still has to hand ordinary callers a
+//! `Runtime` value without them wiring one up themselves. [`RuntimeHandle`]
+//! is that default: every method dispatches through the same
+//! [`spawn_local`]/[`spawn_blocking`]/[`sleep_until`] free functions
+//! `LocalExecutorBuilder::run` already arranges to work, including sleeping
+//! on the active backend's own timer wheel rather than occupying a
+//! blocking-pool thread for the duration of the sleep.
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use super::blocking::{spawn_blocking, BlockingTask};
+use super::dispatch::{sleep_until, spawn_local, LocalSleep, LocalTask};
+use super::runtime::{BlockingPool, Spawner, Timer};
+
+/// A [`Runtime`](super::Runtime) handle for whichever backend the
+/// enclosing [`LocalExecutorBuilder`](super::LocalExecutorBuilder) run is
+/// using.
+///
+/// Zero-sized: every method just forwards to the matching free function,
+/// which itself reads the thread-local state `LocalExecutorBuilder::run`
+/// set up. That makes `RuntimeHandle` cheap to construct anywhere and
+/// correct to pass to [`PipelineDriver`](crate::PipelineDriver) or
+/// [`TransportRunner`](crate::TransportRunner) as their `R: Timer`.
+///
+/// # Panics
+/// Every method panics if called outside of a running
+/// `LocalExecutorBuilder`, same as [`spawn_local`](super::spawn_local).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuntimeHandle;
+
+impl RuntimeHandle {
+    /// Returns a handle to the active runtime.
+    pub fn current() -> Self {
+        Self
+    }
+}
+
+impl Spawner for RuntimeHandle {
+    type JoinHandle<T: 'static> = LocalTask<T>;
+
+    fn spawn_local<F>(&self, fut: F) -> Self::JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+    {
+        spawn_local(fut)
+    }
+}
+
+impl Timer for RuntimeHandle {
+    type Sleep = LocalSleep;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        self.sleep_until(Instant::now() + duration)
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Self::Sleep {
+        sleep_until(deadline)
+    }
+}
+
+impl BlockingPool for RuntimeHandle {
+    type BlockingHandle<T: 'static> = BlockingTask<T>;
+
+    fn spawn_blocking<F, T>(&self, f: F) -> Self::BlockingHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        spawn_blocking(f)
+    }
+}
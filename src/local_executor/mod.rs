@@ -4,8 +4,26 @@
 //! smol or tokio, depending on the enabled feature flags.
 //!
 //! ## Features
-//! - `runtime-smol` (default): Use smol's LocalExecutor
-//! - `runtime-tokio`: Use tokio's LocalSet
+//! - `runtime-smol` (default): Compiles in smol's LocalExecutor
+//! - `runtime-tokio`: Compiles in tokio's LocalSet
+//!
+//! Both may be enabled at once: the features now describe which
+//! backends are *compiled in*, not which one is active. Use
+//! [`RuntimeKind`] and [`LocalExecutorBuilder::runtime`] to pick the
+//! active backend at run time.
+//!
+//! ## Composable runtimes
+//!
+//! Under the hood a runtime is just three small capabilities: [`Spawner`],
+//! [`Timer`], and [`BlockingPool`], unified by the [`Runtime`] trait, so
+//! protocol code (like [`PipelineDriver`](crate::PipelineDriver) and
+//! [`TransportRunner`](crate::TransportRunner)) can stay generic over
+//! `R: Runtime`/`R: Timer` instead of over a specific backend.
+//! [`RuntimeHandle::current`] hands out the default `Runtime` for
+//! whichever backend the enclosing `LocalExecutorBuilder::run` selected.
+//! [`CompoundRuntime`] is there for advanced callers who want to mix
+//! parts from different sources instead, e.g. a real spawner with a mock
+//! timer under test.
 //!
 //! ## Examples
 //!
@@ -46,6 +64,74 @@
 //!     println!("Task returned: {}", result);
 //! });
 //! ```
+//!
+//! ### Picking a Backend at Run Time
+//! ```rust,no_run
+//! use sansio::{LocalExecutorBuilder, RuntimeKind};
+//!
+//! LocalExecutorBuilder::new()
+//!     .runtime(RuntimeKind::Tokio)
+//!     .run(async {
+//!         println!("Running on tokio, even with runtime-smol compiled in!");
+//!     });
+//! ```
+//!
+//! ### Offloading Blocking Work
+//! ```rust,no_run
+//! use sansio::{LocalExecutorBuilder, spawn_blocking};
+//!
+//! LocalExecutorBuilder::default().run(async {
+//!     let task = spawn_blocking(|| {
+//!         std::thread::sleep(std::time::Duration::from_millis(10));
+//!         42
+//!     });
+//!
+//!     let result = task.await;
+//!     println!("Blocking task returned: {}", result);
+//! });
+//! ```
+//!
+//! ### Tuning the Blocking Pool Per Executor
+//! ```rust,no_run
+//! use sansio::LocalExecutorBuilder;
+//! use std::time::Duration;
+//!
+//! LocalExecutorBuilder::new()
+//!     .blocking_pool_size(16)
+//!     .blocking_keep_alive(Duration::from_secs(30))
+//!     .run(async {
+//!         // spawn_blocking calls in here use this executor's pool
+//!         // instead of the shared process-wide default.
+//!     });
+//! ```
+//!
+//! ### Obtaining a `Runtime` for `PipelineDriver`/`TransportRunner`
+//! ```rust,no_run
+//! use sansio::{LocalExecutorBuilder, Pipeline, PipelineDriver, RuntimeHandle};
+//!
+//! LocalExecutorBuilder::default().run(async {
+//!     let pipeline = Pipeline::<Vec<u8>, Vec<u8>>::new().finalize();
+//!     let driver = PipelineDriver::new(pipeline);
+//!     let runtime = RuntimeHandle::current();
+//!     driver.drive(&runtime).await;
+//! });
+//! ```
+
+// =============================================================================
+// Capability traits and compound runtime
+// =============================================================================
+
+mod runtime;
+
+pub use runtime::{BlockingPool, CompoundRuntime, Runtime, Spawner, Timer};
+
+mod handle;
+
+pub use handle::RuntimeHandle;
+
+mod blocking;
+
+pub use blocking::{spawn_blocking, BlockingPoolConfig, BlockingTask, ThreadPool};
 
 // =============================================================================
 // Smol-based implementation
@@ -68,12 +154,18 @@ mod tokio;
 pub use tokio::*;
 
 // =============================================================================
-// Compile-time guards
+// Run-time backend dispatch
 // =============================================================================
 
-// Compile error if neither or both features are enabled
-#[cfg(not(any(feature = "runtime-smol", feature = "runtime-tokio")))]
-compile_error!("Either 'runtime-smol' or 'runtime-tokio' feature must be enabled");
+// `LocalExecutorBuilder` and `spawn_local` below shadow the re-exports from
+// `smol`/`tokio` above, so enabling both backends is no longer ambiguous:
+// the active one is chosen at run time via `RuntimeKind` instead of at
+// compile time via cargo features.
+mod dispatch;
 
-#[cfg(all(feature = "runtime-smol", feature = "runtime-tokio"))]
-compile_error!("Only one runtime feature can be enabled at a time: 'runtime-smol' or 'runtime-tokio'");
+pub use dispatch::{
+    sleep, sleep_until, spawn_local, LocalExecutorBuilder, LocalSleep, LocalTask, RuntimeKind,
+};
+
+#[cfg(not(any(feature = "runtime-smol", feature = "runtime-tokio")))]
+compile_error!("At least one of 'runtime-smol' or 'runtime-tokio' must be enabled");
@@ -0,0 +1,115 @@
+//! Capability traits for composing runtimes from independent parts.
+//!
+//! Rather than one monolithic backend selected by cargo features, a
+//! runtime is expressed as a handful of small capabilities: spawning
+//! local tasks ([`Spawner`]), async sleeping ([`Timer`]), and offloading
+//! synchronous work ([`BlockingPool`]). [`CompoundRuntime`] assembles a
+//! [`Runtime`] from any combination of parts, so protocol code can be
+//! generic over `R: Runtime` instead of over `runtime-smol`/`runtime-tokio`,
+//! and tests can swap in mock parts.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Spawns `'static` futures onto a local (single-threaded) executor.
+pub trait Spawner {
+    /// Handle to a spawned task; resolves to the task's output when awaited.
+    type JoinHandle<T: 'static>: Future<Output = T> + 'static;
+
+    /// Spawns `fut` on the executor, returning a handle to its result.
+    fn spawn_local<F>(&self, fut: F) -> Self::JoinHandle<F::Output>
+    where
+        F: Future + 'static;
+}
+
+/// Provides async sleeping and deadline waiting.
+pub trait Timer {
+    /// Future returned by [`Timer::sleep`] and [`Timer::sleep_until`].
+    type Sleep: Future<Output = ()> + 'static;
+
+    /// Sleeps for `duration`.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+
+    /// Sleeps until `deadline`.
+    fn sleep_until(&self, deadline: Instant) -> Self::Sleep;
+}
+
+/// Offloads blocking (synchronous) work onto a dedicated thread pool.
+pub trait BlockingPool {
+    /// Handle to a task spawned on the blocking pool.
+    type BlockingHandle<T: 'static>: Future<Output = T> + 'static;
+
+    /// Runs `f` on the blocking pool, returning a handle to its result.
+    fn spawn_blocking<F, T>(&self, f: F) -> Self::BlockingHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static;
+}
+
+/// Unified capability surface that protocol code can be generic over.
+///
+/// Blanket-implemented for any type that implements all three capability
+/// traits, so `R: Runtime` is exactly `R: Spawner + Timer + BlockingPool`.
+pub trait Runtime: Spawner + Timer + BlockingPool {}
+
+impl<T> Runtime for T where T: Spawner + Timer + BlockingPool {}
+
+/// A [`Runtime`] assembled from independently swappable parts.
+///
+/// `Sp` supplies [`Spawner`], `Ti` supplies [`Timer`], and `Bl` supplies
+/// [`BlockingPool`], so parts from different sources can be mixed, e.g.
+/// a smol spawner with a mock timer under test. `LocalExecutorBuilder`
+/// does not build one: the default runtime inside a running builder is
+/// [`RuntimeHandle`](super::RuntimeHandle).
+#[derive(Clone, Copy, Default)]
+pub struct CompoundRuntime<Sp, Ti, Bl> {
+    spawner: Sp,
+    timer: Ti,
+    blocking: Bl,
+}
+
+impl<Sp, Ti, Bl> CompoundRuntime<Sp, Ti, Bl> {
+    /// Assembles a runtime from its three parts.
+    pub fn new(spawner: Sp, timer: Ti, blocking: Bl) -> Self {
+        Self {
+            spawner,
+            timer,
+            blocking,
+        }
+    }
+}
+
+impl<Sp: Spawner, Ti, Bl> Spawner for CompoundRuntime<Sp, Ti, Bl> {
+    type JoinHandle<T: 'static> = Sp::JoinHandle<T>;
+
+    fn spawn_local<F>(&self, fut: F) -> Self::JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+    {
+        self.spawner.spawn_local(fut)
+    }
+}
+
+impl<Sp, Ti: Timer, Bl> Timer for CompoundRuntime<Sp, Ti, Bl> {
+    type Sleep = Ti::Sleep;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        self.timer.sleep(duration)
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Self::Sleep {
+        self.timer.sleep_until(deadline)
+    }
+}
+
+impl<Sp, Ti, Bl: BlockingPool> BlockingPool for CompoundRuntime<Sp, Ti, Bl> {
+    type BlockingHandle<T: 'static> = Bl::BlockingHandle<T>;
+
+    fn spawn_blocking<F, T>(&self, f: F) -> Self::BlockingHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.blocking.spawn_blocking(f)
+    }
+}
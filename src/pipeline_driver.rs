@@ -0,0 +1,146 @@
+//! Drives a [`Pipeline`]'s handler timeouts.
+//!
+//! `Handler` exposes `poll_timeout()`/`handle_timeout(now)` so handlers
+//! like retransmission or keepalive timers can schedule themselves, but
+//! nothing previously drove that loop: callers had to hand-roll a sleep
+//! around `poll_timeout()`. [`PipelineDriver`] closes that gap by taking
+//! the earliest deadline across the pipeline's handlers, sleeping until
+//! it fires on the executor's [`Timer`], and calling `handle_timeout`
+//! with the real wake-up `Instant`.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+use crate::select::select2;
+use crate::{Pipeline, Timer};
+
+/// Drives timeouts for a single [`Pipeline`].
+///
+/// Call [`PipelineDriver::drive`] in a spawned task alongside whatever
+/// feeds the pipeline its inbound/outbound traffic, and call
+/// [`PipelineDriver::notify`] any time a handler may have produced an
+/// earlier deadline than the one currently armed (e.g. right after a
+/// `read`/`write` batch), so the driver re-checks `poll_timeout()`
+/// instead of oversleeping.
+pub struct PipelineDriver<Rin: 'static, Win: 'static> {
+    pipeline: Rc<Pipeline<Rin, Win>>,
+    notify: Notify,
+}
+
+impl<Rin: 'static, Win: 'static> PipelineDriver<Rin, Win> {
+    /// Creates a driver for `pipeline`.
+    pub fn new(pipeline: Rc<Pipeline<Rin, Win>>) -> Self {
+        Self {
+            pipeline,
+            notify: Notify::new(),
+        }
+    }
+
+    /// Wakes the driver so it re-evaluates `poll_timeout()` immediately,
+    /// rather than waiting for the previously armed deadline to fire.
+    /// Call this after feeding the pipeline new input, since that may
+    /// have produced an earlier deadline than the one currently slept on.
+    pub fn notify(&self) {
+        self.notify.notify();
+    }
+
+    /// Runs the timeout loop forever; the returned future never
+    /// completes, so it stops only when it is dropped. Intended to be
+    /// spawned alongside the code that feeds the pipeline traffic.
+    ///
+    /// On each iteration: the earliest `poll_timeout()` across the
+    /// pipeline's handlers is taken; if one exists, the driver sleeps
+    /// until it fires *or* until [`Self::notify`] is called, whichever
+    /// comes first. If no handler has an armed timeout, the driver waits
+    /// indefinitely for a notification. Either way, once woken,
+    /// `handle_timeout` is called with the actual `Instant::now()` so a
+    /// handler with multiple overlapping timers can expire all of them
+    /// in one pass.
+    pub async fn drive<R>(&self, runtime: &R)
+    where
+        R: Timer,
+    {
+        loop {
+            self.tick(runtime).await;
+        }
+    }
+
+    /// Runs a single iteration of the timeout loop: sleeps until the
+    /// earliest `poll_timeout()` (or indefinitely if none is armed, or
+    /// until [`Self::notify`] fires first), then calls `handle_timeout`
+    /// with the wake-up `Instant`. Exposed so callers that already run
+    /// their own event loop (like a transport runner) can fold this in
+    /// as one branch of their own select instead of spawning a separate
+    /// task for [`Self::drive`].
+    pub async fn tick<R>(&self, runtime: &R)
+    where
+        R: Timer,
+    {
+        match self.pipeline.poll_timeout() {
+            Some(deadline) => {
+                select2(runtime.sleep_until(deadline), self.notify.notified()).await;
+            }
+            None => {
+                self.notify.notified().await;
+            }
+        }
+        self.pipeline.handle_timeout(Instant::now());
+    }
+}
+
+/// A single-waiter wake-up signal, used to re-arm the driver's sleep
+/// when a handler's deadline may have moved earlier.
+struct Notify {
+    state: Mutex<NotifyState>,
+}
+
+enum NotifyState {
+    Idle,
+    Waiting(Waker),
+    Notified,
+}
+
+impl Notify {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(NotifyState::Idle),
+        }
+    }
+
+    fn notify(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let NotifyState::Waiting(waker) = std::mem::replace(&mut *state, NotifyState::Notified) {
+            waker.wake();
+        }
+    }
+
+    fn notified(&self) -> Notified<'_> {
+        Notified { notify: self }
+    }
+}
+
+struct Notified<'a> {
+    notify: &'a Notify,
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.notify.state.lock().unwrap();
+        match *state {
+            NotifyState::Notified => {
+                *state = NotifyState::Idle;
+                Poll::Ready(())
+            }
+            NotifyState::Idle | NotifyState::Waiting(_) => {
+                *state = NotifyState::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Tiny internal `select!`-style helpers.
+//!
+//! Built on `std::future::poll_fn`/`std::pin::pin!` so event loops like
+//! [`PipelineDriver`](crate::PipelineDriver) and
+//! [`TransportRunner`](crate::TransportRunner) don't need an async
+//! runtime's `select!` macro to race a handful of futures.
+
+use std::future::Future;
+use std::task::Poll;
+
+/// Runs `a` and `b` concurrently, returning as soon as either completes
+/// and dropping the other.
+pub(crate) async fn select2<A, B>(a: A, b: B)
+where
+    A: Future<Output = ()>,
+    B: Future<Output = ()>,
+{
+    let mut a = std::pin::pin!(a);
+    let mut b = std::pin::pin!(b);
+    std::future::poll_fn(move |cx| {
+        if a.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(());
+        }
+        if b.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(());
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Runs three futures concurrently, resolving to whichever completes
+/// first and dropping the other two.
+pub(crate) async fn select3<A, B, C, T>(a: A, b: B, c: C) -> T
+where
+    A: Future<Output = T>,
+    B: Future<Output = T>,
+    C: Future<Output = T>,
+{
+    let mut a = std::pin::pin!(a);
+    let mut b = std::pin::pin!(b);
+    let mut c = std::pin::pin!(c);
+    std::future::poll_fn(move |cx| {
+        if let Poll::Ready(value) = a.as_mut().poll(cx) {
+            return Poll::Ready(value);
+        }
+        if let Poll::Ready(value) = b.as_mut().poll(cx) {
+            return Poll::Ready(value);
+        }
+        if let Poll::Ready(value) = c.as_mut().poll(cx) {
+            return Poll::Ready(value);
+        }
+        Poll::Pending
+    })
+    .await
+}
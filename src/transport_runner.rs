@@ -0,0 +1,309 @@
+//! Puts a [`Pipeline<Vec<u8>, Vec<u8>>`] on the wire.
+//!
+//! The sans-io `Pipeline`/`Handler` design keeps protocol logic free of
+//! I/O, but something still has to read and write the socket.
+//! [`TransportRunner`] is that glue: it owns an async socket and a
+//! finalized pipeline, and runs an event loop that pumps bytes between
+//! them while also driving the pipeline's handler timeouts.
+
+use std::future::Future;
+use std::io;
+use std::rc::Rc;
+
+use crate::select::select3;
+use crate::{Pipeline, PipelineDriver, Timer};
+
+/// The socket half a [`TransportRunner`] drives traffic over.
+///
+/// Implemented for both UDP and TCP so the same runner works over
+/// either: a UDP `recv`/`send` exchanges whole datagrams, a TCP one
+/// whatever bytes are ready/accepted on a given poll.
+pub trait Socket {
+    /// Reads one batch of inbound bytes into `buf`, returning the number
+    /// read. `Ok(0)` signals EOF (meaningful for TCP; UDP sockets don't
+    /// signal EOF and should never return it).
+    fn recv(&mut self, buf: &mut [u8]) -> impl Future<Output = io::Result<usize>>;
+
+    /// Writes `buf` out in full.
+    fn send(&mut self, buf: &[u8]) -> impl Future<Output = io::Result<()>>;
+
+    /// Removes the socket from the executor's reactor before it is
+    /// dropped. Without this, a dropped-but-still-registered fd can be
+    /// reused by the OS for an unrelated socket while the reactor still
+    /// thinks the old registration is live, the same fd-lifecycle hazard
+    /// the gst async-wrapper fix addressed.
+    fn deregister(&mut self);
+}
+
+/// Outbound messages the application wants the pipeline to send.
+///
+/// Callers supply their own implementation (typically backed by
+/// whatever channel their application already uses); [`TransportRunner`]
+/// only needs to wait for the next message, or for the source to close.
+pub trait OutboundSource {
+    /// Waits for the next outbound message. Returns `None` once the
+    /// source is closed, which the runner treats as a request for
+    /// graceful shutdown.
+    fn recv(&mut self) -> impl Future<Output = Option<Vec<u8>>>;
+}
+
+enum Event {
+    Inbound(io::Result<usize>),
+    Outbound(Option<Vec<u8>>),
+    TimerFired,
+}
+
+/// Runs a [`Pipeline<Vec<u8>, Vec<u8>>`] against a real socket.
+///
+/// Each call to [`TransportRunner::run`] loops until the outbound side
+/// closes, the socket reports EOF or the socket errors, selecting over: the socket becoming
+/// readable, the application supplying an outbound message, and the
+/// pipeline's next handler timeout (via [`PipelineDriver`]).
+pub struct TransportRunner<S: Socket> {
+    socket: DeregisterGuard<S>,
+    pipeline: Rc<Pipeline<Vec<u8>, Vec<u8>>>,
+    driver: PipelineDriver<Vec<u8>, Vec<u8>>,
+}
+
+/// Calls [`Socket::deregister`] unconditionally when dropped.
+///
+/// `run`'s own control flow used to call `deregister` on specific exit
+/// paths, but the future `run` returns can be dropped before any of them
+/// are reached (the caller races it against a timeout, or cancels the
+/// task that owns it), which skipped deregistration entirely. Tying it
+/// to `Drop` instead means it always runs exactly once, regardless of
+/// how the runner's owner gives it up.
+struct DeregisterGuard<S: Socket>(S);
+
+impl<S: Socket> std::ops::Deref for DeregisterGuard<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S: Socket> std::ops::DerefMut for DeregisterGuard<S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+}
+
+impl<S: Socket> Drop for DeregisterGuard<S> {
+    fn drop(&mut self) {
+        self.0.deregister();
+    }
+}
+
+impl<S: Socket> TransportRunner<S> {
+    /// Creates a runner for `pipeline` over `socket`.
+    pub fn new(socket: S, pipeline: Rc<Pipeline<Vec<u8>, Vec<u8>>>) -> Self {
+        let driver = PipelineDriver::new(Rc::clone(&pipeline));
+        Self {
+            socket: DeregisterGuard(socket),
+            pipeline,
+            driver,
+        }
+    }
+
+    /// Runs the event loop to completion.
+    ///
+    /// `outbound` feeds application messages into the pipeline;
+    /// `on_inbound` is called with each pipeline output to deliver to
+    /// the application. Returns `Ok(())` once `outbound` closes or the
+    /// socket reports EOF, after flushing any outbound frames the
+    /// pipeline still has queued. A socket error is returned at once,
+    /// without that final flush.
+    pub async fn run<R, Out>(
+        &mut self,
+        runtime: &R,
+        mut outbound: Out,
+        mut on_inbound: impl FnMut(Vec<u8>),
+    ) -> io::Result<()>
+    where
+        R: Timer,
+        Out: OutboundSource,
+    {
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let event = select3(
+                async { Event::Inbound(self.socket.recv(&mut buf).await) },
+                async { Event::Outbound(outbound.recv().await) },
+                async {
+                    self.driver.tick(runtime).await;
+                    Event::TimerFired
+                },
+            )
+            .await;
+
+            match event {
+                Event::Inbound(Ok(0)) => break,
+                Event::Inbound(Ok(n)) => {
+                    self.pipeline.read(buf[..n].to_vec());
+                    while let Some(out) = self.pipeline.poll() {
+                        on_inbound(out);
+                    }
+                    self.flush_outbound().await?;
+                    self.driver.notify();
+                }
+                Event::Inbound(Err(err)) => return Err(err),
+                Event::Outbound(Some(msg)) => {
+                    self.pipeline.write(msg);
+                    self.flush_outbound().await?;
+                    self.driver.notify();
+                }
+                Event::Outbound(None) => break,
+                Event::TimerFired => {
+                    self.flush_outbound().await?;
+                }
+            }
+        }
+
+        self.flush_outbound().await?;
+        Ok(())
+    }
+
+    async fn flush_outbound(&mut self) -> io::Result<()> {
+        while let Some(chunk) = self.pipeline.poll_write() {
+            self.socket.send(&chunk).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Handler;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Wake, Waker};
+    use std::time::Instant;
+
+    struct MockSocket {
+        sent: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Socket for MockSocket {
+        fn recv(&mut self, _buf: &mut [u8]) -> impl Future<Output = io::Result<usize>> {
+            std::future::pending()
+        }
+
+        fn send(&mut self, buf: &[u8]) -> impl Future<Output = io::Result<()>> {
+            self.sent.lock().unwrap().extend_from_slice(buf);
+            std::future::ready(Ok(()))
+        }
+
+        fn deregister(&mut self) {}
+    }
+
+    struct PendingOutbound;
+
+    impl OutboundSource for PendingOutbound {
+        fn recv(&mut self) -> impl Future<Output = Option<Vec<u8>>> {
+            std::future::pending()
+        }
+    }
+
+    /// A timer that never actually waits, so the test drives the event
+    /// loop without depending on real time.
+    struct ImmediateTimer;
+
+    impl Timer for ImmediateTimer {
+        type Sleep = std::future::Ready<()>;
+
+        fn sleep(&self, _duration: std::time::Duration) -> Self::Sleep {
+            std::future::ready(())
+        }
+
+        fn sleep_until(&self, _deadline: Instant) -> Self::Sleep {
+            std::future::ready(())
+        }
+    }
+
+    /// Fires a single handler timeout that queues an outbound message,
+    /// mimicking a retransmission/keepalive handler.
+    struct RetransmitOnce {
+        fired: bool,
+        payload: Option<Vec<u8>>,
+    }
+
+    impl Handler for RetransmitOnce {
+        type Ein = ();
+        type Eout = ();
+        type Rin = Vec<u8>;
+        type Rout = Vec<u8>;
+        type Win = Vec<u8>;
+        type Wout = Vec<u8>;
+        type Error = ();
+
+        fn handle_read(&mut self, _msg: Vec<u8>) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn poll_read(&mut self) -> Option<Vec<u8>> {
+            None
+        }
+
+        fn handle_write(&mut self, _msg: Vec<u8>) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn poll_write(&mut self) -> Option<Vec<u8>> {
+            self.payload.take()
+        }
+
+        fn poll_timeout(&mut self) -> Option<Instant> {
+            if self.fired {
+                None
+            } else {
+                Some(Instant::now())
+            }
+        }
+
+        fn handle_timeout(&mut self, _now: Instant) -> Result<(), ()> {
+            self.fired = true;
+            Ok(())
+        }
+    }
+
+    struct NoopWake;
+
+    impl Wake for NoopWake {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Regression test for the bug where `Event::TimerFired` didn't call
+    /// `flush_outbound`: a handler's timeout-driven write would sit in the
+    /// pipeline until an unrelated application message happened to arrive.
+    #[test]
+    fn timer_fired_flushes_queued_writes() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let pipeline = Pipeline::<Vec<u8>, Vec<u8>>::new();
+        pipeline.add_back(RetransmitOnce {
+            fired: false,
+            payload: Some(b"retransmit".to_vec()),
+        });
+        let pipeline = pipeline.finalize();
+
+        let mut runner = TransportRunner::new(
+            MockSocket {
+                sent: Arc::clone(&sent),
+            },
+            pipeline,
+        );
+
+        let waker = Waker::from(Arc::new(NoopWake));
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = std::pin::pin!(runner.run(&ImmediateTimer, PendingOutbound, |_| {}));
+        for _ in 0..8 {
+            if fut.as_mut().poll(&mut cx).is_ready() {
+                break;
+            }
+            if !sent.lock().unwrap().is_empty() {
+                break;
+            }
+        }
+
+        assert_eq!(&*sent.lock().unwrap(), b"retransmit");
+    }
+}
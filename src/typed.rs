@@ -7,7 +7,9 @@
 //!
 //! It builds on top of the existing dynamic `Pipeline`, so runtime
 //! behavior and performance remain the same while offering compile-time
-//! safety during composition.
+//! safety during composition. Splicing two builders together
+//! (`splice`/`insert_before`/`add_front`) reuses `Pipeline::append`
+//! alongside the existing `add_back` to merge their handler chains.
 
 use crate::{Handler, Pipeline};
 use std::{marker::PhantomData, rc::Rc};
@@ -85,6 +87,70 @@ impl<R: 'static, W: 'static, Prev: HandlerSig> TypedPipelineBuilder<R, W, Prev>
         }
     }
 
+    /// Prepends a handler to the front of the pipeline, shifting the
+    /// builder's external boundary types (`R`, `W`) to `handler`'s,
+    /// enforcing that:
+    /// - Inbound types align: `H::Rout == R`
+    /// - Outbound types align: `H::Win == W`
+    ///
+    /// Lets a reusable front-of-chain handler (e.g. length framing) be
+    /// composed onto a pipeline that was built independently of it.
+    pub fn add_front<H>(self, handler: H) -> TypedPipelineBuilder<H::Rin, H::Wout, Prev>
+    where
+        H: Handler + 'static,
+        (): AssertEqual<<H as HandlerSig>::Rout, R>,
+        (): AssertEqual<<H as HandlerSig>::Win, W>,
+    {
+        TypedPipelineBuilder::<H::Rin, H::Wout, Start<H::Rin, H::Wout>>::new()
+            .add_back(handler)
+            .splice(self)
+    }
+
+    /// Splices `next`, a pipeline built independently starting at
+    /// `Start<Prev::Rout, Prev::Win>`, onto the back of `self`, producing
+    /// one pipeline that runs `self`'s handlers followed by `next`'s.
+    /// The seam is proven by `next`'s own type: it can only be passed
+    /// here if its declared boundary types equal `self`'s current tail.
+    ///
+    /// Lets reusable, independently type-checked protocol modules be
+    /// built as their own `TypedPipelineBuilder`s and then glued
+    /// together instead of redeclared inline.
+    pub fn splice<NextPrev>(
+        self,
+        next: TypedPipelineBuilder<Prev::Rout, Prev::Win, NextPrev>,
+    ) -> TypedPipelineBuilder<R, W, NextPrev>
+    where
+        NextPrev: HandlerSig,
+    {
+        self.inner.append(next.inner);
+        TypedPipelineBuilder {
+            inner: self.inner,
+            _prev: PhantomData::<NextPrev>,
+        }
+    }
+
+    /// Inserts `handler` as the seam between `self` and `next`, enforcing
+    /// both joins at once:
+    /// - `self`'s tail feeds `handler`: `Prev::Rout == H::Rin`, `H::Wout == Prev::Win`
+    /// - `handler` feeds `next`'s head: enforced by `next`'s declared type
+    ///
+    /// Useful for injecting a tap (logging, metrics, TLS) between two
+    /// independently built pipeline segments while preserving
+    /// compile-time adjacency on both sides of the seam.
+    pub fn insert_before<H, NextPrev>(
+        self,
+        handler: H,
+        next: TypedPipelineBuilder<H::Rout, H::Win, NextPrev>,
+    ) -> TypedPipelineBuilder<R, W, NextPrev>
+    where
+        H: Handler + 'static,
+        (): AssertEqual<<Prev as HandlerSig>::Rout, <H as HandlerSig>::Rin>,
+        (): AssertEqual<<H as HandlerSig>::Wout, <Prev as HandlerSig>::Win>,
+        NextPrev: HandlerSig,
+    {
+        self.add_back(handler).splice(next)
+    }
+
     /// Finalizes and returns the underlying `Rc<Pipeline<R, W>>`.
     pub fn build(self) -> Rc<Pipeline<R, W>> {
         self.inner.finalize()
@@ -96,3 +162,77 @@ impl<R: 'static, W: 'static, Prev: HandlerSig> TypedPipelineBuilder<R, W, Prev>
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// A no-op handler that passes messages through unchanged, just to
+    /// exercise the builder's type-level plumbing without any real
+    /// protocol logic.
+    struct Identity<T> {
+        read: VecDeque<T>,
+        write: VecDeque<T>,
+    }
+
+    impl<T> Identity<T> {
+        fn new() -> Self {
+            Self {
+                read: VecDeque::new(),
+                write: VecDeque::new(),
+            }
+        }
+    }
+
+    impl<T: 'static> Handler for Identity<T> {
+        type Ein = ();
+        type Eout = ();
+        type Rin = T;
+        type Rout = T;
+        type Win = T;
+        type Wout = T;
+        type Error = ();
+
+        fn handle_read(&mut self, msg: T) -> Result<(), ()> {
+            self.read.push_back(msg);
+            Ok(())
+        }
+
+        fn poll_read(&mut self) -> Option<T> {
+            self.read.pop_front()
+        }
+
+        fn handle_write(&mut self, msg: T) -> Result<(), ()> {
+            self.write.push_back(msg);
+            Ok(())
+        }
+
+        fn poll_write(&mut self) -> Option<T> {
+            self.write.pop_front()
+        }
+    }
+
+    #[test]
+    fn splice_joins_two_independently_built_segments() {
+        let front =
+            TypedPipelineBuilder::<Vec<u8>, Vec<u8>, _>::new().add_back(Identity::new());
+        let back =
+            TypedPipelineBuilder::<Vec<u8>, Vec<u8>, Start<Vec<u8>, Vec<u8>>>::new()
+                .add_back(Identity::new());
+        let pipeline = front.splice(back).build();
+
+        pipeline.read(b"hello".to_vec());
+        assert_eq!(pipeline.poll(), Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn add_front_prepends_a_handler_and_preserves_end_to_end_flow() {
+        let inner =
+            TypedPipelineBuilder::<Vec<u8>, Vec<u8>, _>::new().add_back(Identity::new());
+        let pipeline = inner.add_front(Identity::<Vec<u8>>::new()).build();
+
+        pipeline.read(b"hello".to_vec());
+        assert_eq!(pipeline.poll(), Some(b"hello".to_vec()));
+    }
+}